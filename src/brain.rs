@@ -0,0 +1,289 @@
+use rand::prelude::*;
+use rand_distr::{Distribution, StandardNormal};
+
+use crate::game::{Controls, Game};
+
+/// A plain row-major matrix, used to hold a single layer's weights.
+#[derive(Clone)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f32>,
+}
+
+impl Matrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> f32 {
+        self.data[row * self.cols + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: f32) {
+        self.data[row * self.cols + col] = value;
+    }
+
+    /// Multiplies this matrix by a column vector.
+    pub fn mul_vec(&self, input: &[f32]) -> Vec<f32> {
+        let mut out = vec![0.0; self.rows];
+
+        for r in 0..self.rows {
+            let mut sum = 0.0;
+            for c in 0..self.cols {
+                sum += self.get(r, c) * input[c];
+            }
+            out[r] = sum;
+        }
+
+        out
+    }
+}
+
+/// A simple feedforward network: `config` gives the layer sizes (including the
+/// input and output layers), `weights[i]` maps layer `i` to layer `i+1` with an
+/// extra column for the bias term.
+#[derive(Clone)]
+pub struct NN {
+    config: Vec<usize>,
+    weights: Vec<Matrix>,
+}
+
+impl NN {
+    /// Builds a network for `config` with He-initialized weights.
+    pub fn new(config: Vec<usize>) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut weights = Vec::with_capacity(config.len() - 1);
+
+        for i in 1..config.len() {
+            let last = config[i - 1];
+            let curr = config[i];
+            let scale = (2.0 / last as f32).sqrt();
+
+            let mut matrix = Matrix::new(curr, last + 1);
+            for r in 0..curr {
+                for c in 0..(last + 1) {
+                    let value: f32 = rng.sample(StandardNormal);
+                    matrix.set(r, c, value * scale);
+                }
+            }
+
+            weights.push(matrix);
+        }
+
+        Self { config, weights }
+    }
+
+    pub fn config(&self) -> &[usize] {
+        &self.config
+    }
+
+    /// Runs a forward pass, appending the bias term and applying ReLU after
+    /// every layer.
+    pub fn forward(&self, input: &[f32]) -> Vec<f32> {
+        let mut activations = input.to_vec();
+
+        for matrix in &self.weights {
+            activations.push(1.0); // bias
+            activations = matrix.mul_vec(&activations);
+
+            for value in &mut activations {
+                *value = value.max(0.0); // ReLU
+            }
+        }
+
+        activations
+    }
+
+    fn crossover(a: &NN, b: &NN, rng: &mut ThreadRng) -> NN {
+        let mut weights = Vec::with_capacity(a.weights.len());
+
+        for (wa, wb) in a.weights.iter().zip(&b.weights) {
+            let mut child = wa.clone();
+            for i in 0..child.data.len() {
+                if rng.gen_bool(0.5) {
+                    child.data[i] = wb.data[i];
+                }
+            }
+            weights.push(child);
+        }
+
+        Self { config: a.config.clone(), weights }
+    }
+
+    /// Replaces mutated weights with a fresh He-scaled sample rather than a
+    /// raw standard-normal draw, so a mutation perturbs around the layer's
+    /// existing weight distribution instead of landing off-scale (the bias
+    /// column included, since `matrix.cols - 1` recovers `last`, the same
+    /// fan-in `new()` scaled by).
+    fn mutate(&mut self, rng: &mut ThreadRng) {
+        for matrix in &mut self.weights {
+            let scale = (2.0 / (matrix.cols - 1) as f32).sqrt();
+
+            for value in &mut matrix.data {
+                if rng.gen_bool(Population::MUTATION_RATE as f64) {
+                    let sample: f32 = rng.sample(StandardNormal);
+                    *value = sample * scale;
+                }
+            }
+        }
+    }
+}
+
+impl Controls {
+    /// Maps a network's 4 outputs onto the rotate-left/rotate-right/thrust/fire
+    /// controls. `fire` is edge-triggered off `was_firing` (the previous call's
+    /// raw fire output) so a brain holding its fire neuron positive gets the
+    /// same one-shot-per-press semantics as `from_keyboard`'s `is_key_pressed`,
+    /// instead of spraying a bullet every frame. Returns the controls plus the
+    /// raw fire output to pass back in as `was_firing` on the next call.
+    pub fn from_outputs(outputs: &[f32], was_firing: bool) -> (Self, bool) {
+        let firing = outputs[3] > 0.0;
+
+        let controls = Self {
+            rotate_left: outputs[0] > 0.0,
+            rotate_right: outputs[1] > 0.0,
+            thrust: outputs[2] > 0.0,
+            fire: firing && !was_firing,
+        };
+
+        (controls, firing)
+    }
+}
+
+/// A generation of AI pilots trained with a genetic algorithm instead of
+/// backpropagation: each brain plays a headless game, is scored by a fitness
+/// function, and the fittest are bred into the next generation.
+pub struct Population {
+    brains: Vec<NN>,
+    generation: u32,
+}
+
+impl Population {
+    const MUTATION_RATE: f32 = 0.04;
+    const ASTEROID_WEIGHT: f32 = 10.0;
+    const STEP: f32 = 1.0 / 60.0;
+
+    pub fn new(size: usize, config: Vec<usize>) -> Self {
+        let brains = (0..size).map(|_| NN::new(config.clone())).collect();
+        Self { brains, generation: 0 }
+    }
+
+    pub fn brains(&self) -> &[NN] {
+        &self.brains
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Plays one headless game per brain (each brain's lifespan ending in a
+    /// collision, or `max_frames` elapsing) and returns its fitness score,
+    /// `lifespan_frames + k * asteroids_destroyed`. `sense` builds the network's
+    /// input vector from the current game state.
+    pub fn run_generation(&mut self, max_frames: u32, sense: impl Fn(&Game) -> Vec<f32>) -> Vec<f32> {
+        self.brains.iter().map(|brain| Self::evaluate(brain, max_frames, &sense)).collect()
+    }
+
+    fn evaluate(brain: &NN, max_frames: u32, sense: &impl Fn(&Game) -> Vec<f32>) -> f32 {
+        let mut game = Game::new();
+        game.levelup();
+
+        let start_destroyed = game.destroyed;
+        let mut frames = 0;
+        let mut was_firing = false;
+
+        while frames < max_frames && game.deaths == 0 {
+            let outputs = brain.forward(&sense(&game));
+            let (controls, firing) = Controls::from_outputs(&outputs, was_firing);
+            was_firing = firing;
+
+            game.update_headless(Self::STEP, controls);
+            frames += 1;
+        }
+
+        frames as f32 + Self::ASTEROID_WEIGHT * (game.destroyed - start_destroyed) as f32
+    }
+
+    /// Breeds the next generation from `scores` (one fitness value per current
+    /// brain): the fittest half act as parents, each child is produced by
+    /// crossover followed by mutation, and the best brain is carried over
+    /// unchanged (elitism).
+    pub fn evolve(&mut self, scores: &[f32]) {
+        let mut rng = rand::thread_rng();
+
+        let mut ranked: Vec<usize> = (0..self.brains.len()).collect();
+        ranked.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap());
+
+        let parent_pool = (ranked.len() / 2).max(1);
+
+        let mut next = Vec::with_capacity(self.brains.len());
+        next.push(self.brains[ranked[0]].clone());
+
+        while next.len() < self.brains.len() {
+            let a = &self.brains[ranked[rng.gen_range(0..parent_pool)]];
+            let b = &self.brains[ranked[rng.gen_range(0..parent_pool)]];
+
+            let mut child = NN::crossover(a, b, &mut rng);
+            child.mutate(&mut rng);
+            next.push(child);
+        }
+
+        self.brains = next;
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_mul_vec_multiplies_rows_by_the_input() {
+        let mut m = Matrix::new(2, 3);
+        m.set(0, 0, 1.0); m.set(0, 1, 2.0); m.set(0, 2, 3.0);
+        m.set(1, 0, 0.0); m.set(1, 1, 1.0); m.set(1, 2, 0.0);
+
+        assert_eq!(m.mul_vec(&[1.0, 1.0, 1.0]), vec![6.0, 1.0]);
+    }
+
+    #[test]
+    fn forward_output_matches_the_final_layer_size_and_clamps_negatives() {
+        let mut nn = NN::new(vec![2, 3, 1]);
+        for value in &mut nn.weights[1].data {
+            *value = -1.0; // force the output layer negative before ReLU
+        }
+
+        let output = nn.forward(&[1.0, 1.0]);
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0], 0.0);
+    }
+
+    #[test]
+    fn crossover_only_takes_values_from_either_parent() {
+        let mut rng = rand::thread_rng();
+
+        let mut a = NN::new(vec![2, 2]);
+        let mut b = NN::new(vec![2, 2]);
+        for value in &mut a.weights[0].data { *value = 1.0; }
+        for value in &mut b.weights[0].data { *value = 2.0; }
+
+        let child = NN::crossover(&a, &b, &mut rng);
+
+        for value in &child.weights[0].data {
+            assert!(*value == 1.0 || *value == 2.0);
+        }
+    }
+
+    #[test]
+    fn from_outputs_edge_triggers_fire_instead_of_firing_every_frame() {
+        let held_high = [0.0, 0.0, 0.0, 1.0];
+
+        let (first, was_firing) = Controls::from_outputs(&held_high, false);
+        assert!(first.fire);
+
+        let (second, _) = Controls::from_outputs(&held_high, was_firing);
+        assert!(!second.fire);
+    }
+}
@@ -32,9 +32,45 @@ impl<const SIZE: usize> Model<SIZE>  {
         return batch;
     }
 
+    /// Renders the shape, plus extra copies offset by `±WIDTH`/`±HEIGHT`
+    /// whenever a vertex falls outside the play field, so shapes straddling a
+    /// wrap seam don't pop in/out at the edge.
     pub fn render(&self,d: &mut impl RaylibDraw)
     {
-        d.draw_line_strip(&self.draw_points(), self.color);
+        let points = self.draw_points();
+
+        let mut min_x = f32::MAX;
+        let mut max_x = f32::MIN;
+        let mut min_y = f32::MAX;
+        let mut max_y = f32::MIN;
+
+        for p in &points {
+            min_x = min_x.min(p.x);
+            max_x = max_x.max(p.x);
+            min_y = min_y.min(p.y);
+            max_y = max_y.max(p.y);
+        }
+
+        let width = Game::WIDTH as f32;
+        let height = Game::HEIGHT as f32;
+
+        let mut x_offsets = vec![0.0];
+        if min_x < 0.0 { x_offsets.push(width); }
+        if max_x > width { x_offsets.push(-width); }
+
+        let mut y_offsets = vec![0.0];
+        if min_y < 0.0 { y_offsets.push(height); }
+        if max_y > height { y_offsets.push(-height); }
+
+        // cross the two axes so a shape straddling a screen corner also gets
+        // the diagonal (±WIDTH, ±HEIGHT) copy, not just the axis-aligned ones
+        for &ox in &x_offsets {
+            for &oy in &y_offsets {
+                let offset = Vector2::new(ox, oy);
+                let shifted: Vec<Vector2> = points.iter().map(|p| *p + offset).collect();
+                d.draw_line_strip(&shifted, self.color);
+            }
+        }
     }
 
     pub fn get_direction(&self) -> Vector2
@@ -72,6 +108,10 @@ impl Player {
     const SPEED: f32 = 5.0;
     const MAX_SPEED: f32 = 300.0;
     const ROTATIONAL_SPEED: f32 = 7.5;
+    const RADIUS: f32 = 15.0;
+
+    pub const RAY_COUNT: usize = 8;
+    const RAY_RANGE: f32 = 400.0;
 
     const POINTS: [Vector2; 4] = [
         Vector2 {x: 0.0, y: -20.0},
@@ -79,6 +119,48 @@ impl Player {
         Vector2 {x: 0.0, y: 5.0},
         Vector2 {x: 10.0, y: 10.0}
     ];
+
+    fn ray_direction(&self,index: usize) -> Vector2 {
+        let mut dir = self.model.get_direction();
+        dir.rotate(index as f32 * (PI / 4.0));
+        dir
+    }
+
+    /// Casts `RAY_COUNT` directional sensors, evenly spaced `PI/4` apart around
+    /// the player's heading, and returns the normalized distance (0 = level with
+    /// the player, 1 = nothing within `RAY_RANGE`) to the nearest asteroid along
+    /// each ray, measured to the asteroid's centre projected onto the ray.
+    /// This is the autopilot's network input vector.
+    pub fn sense(&self,asteroids: &[Asteroid]) -> Vec<f32> {
+        (0..Self::RAY_COUNT).map(|i| {
+            let dir = self.ray_direction(i);
+            let mut nearest = Self::RAY_RANGE;
+
+            for asteroid in asteroids {
+                let v = asteroid.model.position - self.model.position;
+                let cross = v.x * dir.y - v.y * dir.x;
+                let dot = v.x * dir.x + v.y * dir.y;
+
+                if cross.abs() <= asteroid.radius() && dot >= 0.0 && dot < nearest {
+                    nearest = dot;
+                }
+            }
+
+            nearest / Self::RAY_RANGE
+        }).collect()
+    }
+
+    /// Debug visualization of `sense()` — draws each ray out to the distance it
+    /// reports, so you can see what the autopilot "sees".
+    pub fn render_rays(&self,d: &mut impl RaylibDraw,asteroids: &[Asteroid]) {
+        let distances = self.sense(asteroids);
+
+        for i in 0..Self::RAY_COUNT {
+            let dir = self.ray_direction(i);
+            let end = self.model.position + dir * (distances[i] * Self::RAY_RANGE);
+            d.draw_line_v(self.model.position, end, Color::RED);
+        }
+    }
 }
 
 impl Entity for Player {
@@ -137,16 +219,8 @@ impl Asteroid {
     const DEFAULT_MAX: f32 = 80.0;
     const DEFAULT_MIN: f32 = 20.0;
     const SPEED: f32 = 100.0;
-}
-
-impl Entity for Asteroid {
-
-    fn spawn(position: Vector2) -> Self {
-        let mut rng = rand::thread_rng();
-
-        let class = AsteroidType::random(&mut rng);
-        let direction = Vector2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
 
+    fn generate_points(class: &AsteroidType, rng: &mut ThreadRng) -> [Vector2; 10] {
         let mut points = [Vector2::zero(); 10];
 
         let increment = (PI * 2.0) / (points.len() - 1) as f32;
@@ -161,6 +235,71 @@ impl Entity for Asteroid {
             current += increment;
         }
 
+        points
+    }
+
+    /// Spawns a fragment at `position` travelling in `direction`, used when a larger
+    /// asteroid is destroyed and degrades into smaller pieces.
+    pub fn spawn_fragment(position: Vector2, direction: Vector2, class: AsteroidType) -> Self {
+        let mut rng = rand::thread_rng();
+        let points = Self::generate_points(&class, &mut rng);
+
+        Self { model: Model::new(points, position), direction, class }
+    }
+
+    pub fn radius(&self) -> f32 {
+        Self::DEFAULT_MAX * self.class.size()
+    }
+
+    /// Spawns an asteroid at `position` with its direction aimed at `target`
+    /// instead of drifting randomly, e.g. to send some of a wave homing
+    /// toward the player's position at the start of a level. `direction` is
+    /// normalized to a unit vector before `apply()`'s `* Self::SPEED / class.size()`
+    /// scaling; `spawn()`'s random direction is not normalized, so its speed varies.
+    pub fn spawn_toward(position: Vector2, target: Vector2) -> Self {
+        let mut rng = rand::thread_rng();
+
+        let class = AsteroidType::random(&mut rng);
+        let mut direction = target - position;
+        direction.normalize();
+
+        let points = Self::generate_points(&class, &mut rng);
+
+        Self { model: Model::new(points, position), direction, class }
+    }
+
+    /// Splits this asteroid into its next-smaller fragments, or an empty vec if it
+    /// was already at the smallest size.
+    pub fn split(&self) -> Vec<Asteroid> {
+        match self.class.degrade() {
+            Some(next) => {
+                const SPREAD: f32 = PI / 4.0;
+
+                let mut left = self.direction;
+                left.rotate(SPREAD);
+
+                let mut right = self.direction;
+                right.rotate(-SPREAD);
+
+                vec![
+                    Asteroid::spawn_fragment(self.model.position, left, next.clone()),
+                    Asteroid::spawn_fragment(self.model.position, right, next),
+                ]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Entity for Asteroid {
+
+    fn spawn(position: Vector2) -> Self {
+        let mut rng = rand::thread_rng();
+
+        let class = AsteroidType::random(&mut rng);
+        let direction = Vector2::new(rng.gen_range(-1.0..=1.0), rng.gen_range(-1.0..=1.0));
+        let points = Self::generate_points(&class, &mut rng);
+
         Self { model: Model::new(points, position), direction, class }
     }
 
@@ -174,10 +313,160 @@ impl Entity for Asteroid {
     }
 }
 
-pub struct Game 
+pub struct Bullet
+{
+    model: Model<2>,
+    direction: Vector2,
+    lifetime: f32,
+}
+
+impl Bullet {
+    const SPEED: f32 = 500.0;
+    const LIFETIME: f32 = 1.2;
+    const RADIUS: f32 = 3.0;
+
+    const POINTS: [Vector2; 2] = [
+        Vector2 {x: 0.0, y: -3.0},
+        Vector2 {x: 0.0, y: 3.0}
+    ];
+
+    /// Fires a bullet from `position` travelling along `direction`, e.g. the
+    /// player's `get_direction()`.
+    pub fn fire(position: Vector2, direction: Vector2) -> Self {
+        Self { model: Model::new(Self::POINTS, position), direction, lifetime: Self::LIFETIME }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.lifetime <= 0.0
+    }
+}
+
+impl Entity for Bullet {
+
+    fn spawn(position: Vector2) -> Self {
+        Self::fire(position, Vector2::new(0.0, -1.0))
+    }
+
+    fn render(&self,d: &mut impl RaylibDraw) {
+        self.model.render(d);
+    }
+
+    fn apply(&mut self,delta_time: f32) {
+        self.model.position += self.direction * Self::SPEED * delta_time;
+        self.model.apply_constraints();
+        self.lifetime -= delta_time;
+    }
+}
+
+/// A uniform spatial grid over the play field, used as a collision broadphase:
+/// asteroids are bucketed into every cell their bounding circle overlaps, so a
+/// bullet or the player only needs to test the handful of asteroids sharing or
+/// neighboring its own cell instead of every asteroid in the game.
+struct Grid {
+    cols: usize,
+    rows: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl Grid {
+    // Tuned to a Large asteroid's radius (DEFAULT_MAX * Large::size()) so a
+    // bounding circle never spans more than its own cell plus one neighbor on
+    // each axis.
+    const CELL_SIZE: f32 = Asteroid::DEFAULT_MAX * 2.0;
+
+    fn dimensions() -> (usize, usize) {
+        let cols = (Game::WIDTH as f32 / Self::CELL_SIZE).ceil().max(1.0) as usize;
+        let rows = (Game::HEIGHT as f32 / Self::CELL_SIZE).ceil().max(1.0) as usize;
+        (cols, rows)
+    }
+
+    fn cell_of(position: Vector2,cols: usize,rows: usize) -> (usize, usize) {
+        let cx = (position.x / Self::CELL_SIZE).floor() as isize;
+        let cy = (position.y / Self::CELL_SIZE).floor() as isize;
+        (cx.rem_euclid(cols as isize) as usize, cy.rem_euclid(rows as isize) as usize)
+    }
+
+    /// Every cell index from `min` to `max` (inclusive) along an axis of
+    /// length `len`, wrapping around the seam when `min > max` (i.e. the span
+    /// straddles the edge of the field).
+    fn axis_range(min: usize,max: usize,len: usize) -> Vec<usize> {
+        if min <= max {
+            (min..=max).collect()
+        } else {
+            (min..len).chain(0..=max).collect()
+        }
+    }
+
+    fn build(asteroids: &[Asteroid]) -> Self {
+        let (cols, rows) = Self::dimensions();
+        let mut cells = vec![Vec::new(); cols * rows];
+
+        for (index, asteroid) in asteroids.iter().enumerate() {
+            let radius = asteroid.radius();
+            let min = Self::cell_of(asteroid.model.position - Vector2::new(radius, radius), cols, rows);
+            let max = Self::cell_of(asteroid.model.position + Vector2::new(radius, radius), cols, rows);
+
+            // cover every cell the bounding box spans, not just its corners
+            for cx in Self::axis_range(min.0, max.0, cols) {
+                for &cy in &Self::axis_range(min.1, max.1, rows) {
+                    cells[cy * cols + cx].push(index);
+                }
+            }
+        }
+
+        for cell in &mut cells {
+            cell.sort_unstable();
+            cell.dedup();
+        }
+
+        Self { cols, rows, cells }
+    }
+
+    /// Every asteroid index bucketed into `position`'s cell or one of its 8
+    /// neighbors.
+    fn neighbors(&self,position: Vector2) -> Vec<usize> {
+        let (cx, cy) = Self::cell_of(position, self.cols, self.rows);
+        let mut out = Vec::new();
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let nx = (cx as isize + dx).rem_euclid(self.cols as isize) as usize;
+                let ny = (cy as isize + dy).rem_euclid(self.rows as isize) as usize;
+                out.extend_from_slice(&self.cells[ny * self.cols + nx]);
+            }
+        }
+
+        out.sort_unstable();
+        out.dedup();
+        out
+    }
+}
+
+/// The inputs a frame of `Game::step` reacts to, whether they came from the
+/// keyboard or from a `brain::NN`'s forward pass.
+pub struct Controls {
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    pub thrust: bool,
+    pub fire: bool,
+}
+
+impl Controls {
+    pub fn from_keyboard(rl: &RaylibHandle) -> Self {
+        Self {
+            rotate_left: rl.is_key_down(KeyboardKey::KEY_A),
+            rotate_right: rl.is_key_down(KeyboardKey::KEY_D),
+            thrust: rl.is_key_down(KeyboardKey::KEY_W),
+            fire: rl.is_key_pressed(KeyboardKey::KEY_SPACE),
+        }
+    }
+}
+
+pub struct Game
 {
     pub player: Player,
     pub asteroids: Vec<Asteroid>,
+    pub bullets: Vec<Bullet>,
     level: u32,
     // all time is in seconds
     pause: bool,
@@ -185,6 +474,10 @@ pub struct Game
     pause_end: f64,
     // UI components
     show_level: bool,
+    debug: bool,
+    // training/stat counters
+    pub destroyed: u32,
+    pub deaths: u32,
 }
 
 impl Game {
@@ -195,10 +488,33 @@ impl Game {
     const MID_X: f32 = Game::WIDTH as f32 / 2.0;
     const MID_Y: f32 = Game::HEIGHT as f32 / 2.0;
 
+    /// Squared distance between `a` and `b` on the toroidal (wrapping) play
+    /// field: the shorter of the direct and wrap-around paths on each axis.
+    fn toroidal_distance_sq(a: Vector2,b: Vector2) -> f32 {
+        let dx = (a.x - b.x).abs();
+        let dx = dx.min(Self::WIDTH as f32 - dx);
+
+        let dy = (a.y - b.y).abs();
+        let dy = dy.min(Self::HEIGHT as f32 - dy);
+
+        dx * dx + dy * dy
+    }
+
     pub fn new() -> Self {
         let player = Player::spawn(Vector2::new(Game::WIDTH as f32 / 2.0, Game::HEIGHT as f32 / 2.0));
 
-        Self { player, asteroids: Vec::new(), level: 0, pause: false, pause_time: 0.0, pause_end: 0.0, show_level: false }
+        Self { player, asteroids: Vec::new(), bullets: Vec::new(), level: 0, pause: false, pause_time: 0.0, pause_end: 0.0, show_level: false, debug: false, destroyed: 0, deaths: 0 }
+    }
+
+    /// Ends the run and resets the game back to a fresh level one, e.g. after the
+    /// player collides with an asteroid.
+    pub fn reset(&mut self) {
+        self.player = Player::spawn(Vector2::new(Self::MID_X, Self::MID_Y));
+        self.asteroids.clear();
+        self.bullets.clear();
+        self.level = 0;
+
+        self.levelup();
     }
 
     pub fn levelup(&mut self){
@@ -210,7 +526,13 @@ impl Game {
         let mut rng = rand::thread_rng();
 
         const CLOSEST: f32 = 150.0;
-        
+        // fraction of the wave that homes toward the player instead of
+        // drifting randomly, ramping up with level up to a cap
+        const AIMED_PER_LEVEL: f32 = 0.08;
+        const MAX_AIMED_FRACTION: f32 = 0.6;
+
+        let aimed_fraction = (self.level as f32 * AIMED_PER_LEVEL).min(MAX_AIMED_FRACTION);
+
         let increment = (PI * 2.0) / count;
         let mut current: f32 = 0.0;
 
@@ -219,8 +541,13 @@ impl Game {
         for _ in 0..count as u32 {
             let x = current.sin() * rng.gen_range(CLOSEST..(CLOSEST*2.0)) + Self::MID_X;
             let y = current.cos() * rng.gen_range(CLOSEST..(CLOSEST*2.0)) + Self::MID_Y;
+            let position = Vector2 { x, y };
 
-            let asteriod = Asteroid::spawn(Vector2 { x, y });
+            let asteriod = if rng.gen_bool(aimed_fraction as f64) {
+                Asteroid::spawn_toward(position, self.player.model.position)
+            } else {
+                Asteroid::spawn(position)
+            };
 
             self.asteroids.push(asteriod);
 
@@ -234,6 +561,13 @@ impl Game {
 
     pub fn update(&mut self,rl: &RaylibHandle){
 
+        // checked before the pause early-return below so a press during the
+        // "Level: N" screen isn't dropped (is_key_pressed only fires on the
+        // transition frame, which the pause would otherwise swallow)
+        if rl.is_key_pressed(KeyboardKey::KEY_F1) {
+            self.debug = !self.debug;
+        }
+
         if self.pause {
             if self.pause_time == 0.0 {
                 self.pause_time = rl.get_time();
@@ -247,29 +581,112 @@ impl Game {
         }
 
         let dt = rl.get_frame_time();
+        self.step(dt, Controls::from_keyboard(rl));
+    }
 
-        if rl.is_key_down(KeyboardKey::KEY_A)
+    /// Advances the simulation by a fixed `dt` using externally supplied
+    /// `controls`, bypassing the vsync-gated pause/timing above so a
+    /// `brain::Population` can fast-forward through many generations.
+    pub fn update_headless(&mut self,dt: f32,controls: Controls){
+        if self.pause {
+            self.pause = false;
+            self.show_level = false;
+            self.pause_time = 0.0;
+        }
+        self.step(dt, controls);
+    }
+
+    fn step(&mut self,dt: f32,controls: Controls){
+
+        if controls.rotate_left
         {
             self.player.model.rotation -= Player::ROTATIONAL_SPEED * dt;
         }
-        if rl.is_key_down(KeyboardKey::KEY_D)
+        if controls.rotate_right
         {
             self.player.model.rotation += Player::ROTATIONAL_SPEED * dt;
         }
-        if rl.is_key_down(KeyboardKey::KEY_W)
+        if controls.thrust
         {
             self.player.force += self.player.model.get_direction() * Player::SPEED;
             self.player.force = self.player.force.clamp(-Player::MAX_SPEED..Player::MAX_SPEED);
-        } else 
+        } else
         {
             // drag
             self.player.force.scale(0.98);
         }
+        if controls.fire
+        {
+            self.bullets.push(Bullet::fire(self.player.model.position, self.player.model.get_direction()));
+        }
         // apply physics
         self.player.apply(dt);
         for asteroid in &mut self.asteroids {
             asteroid.apply(dt);
         }
+
+        // broadphase: bucket asteroids into a grid so bullet/player collision
+        // checks only need to look at nearby cells instead of every asteroid
+        let grid = Grid::build(&self.asteroids);
+
+        let mut asteroid_hit = vec![false; self.asteroids.len()];
+        let mut bullet_spent = vec![false; self.bullets.len()];
+        let mut fragments: Vec<Asteroid> = Vec::new();
+
+        // bullets vs asteroids
+        for (i, bullet) in self.bullets.iter_mut().enumerate() {
+            bullet.apply(dt);
+
+            if bullet.is_expired() {
+                bullet_spent[i] = true;
+                continue;
+            }
+
+            for j in grid.neighbors(bullet.model.position) {
+                if asteroid_hit[j] {
+                    continue;
+                }
+
+                let asteroid = &self.asteroids[j];
+                let radius = Bullet::RADIUS + asteroid.radius();
+                let dist_sq = Self::toroidal_distance_sq(bullet.model.position, asteroid.model.position);
+
+                if dist_sq <= radius * radius {
+                    asteroid_hit[j] = true;
+                    bullet_spent[i] = true;
+                    fragments.extend(asteroid.split());
+                    self.destroyed += 1;
+                    break;
+                }
+            }
+        }
+
+        // player vs asteroids
+        for j in grid.neighbors(self.player.model.position) {
+            if asteroid_hit[j] {
+                continue;
+            }
+
+            let asteroid = &self.asteroids[j];
+            let radius = Player::RADIUS + asteroid.radius();
+            let dist_sq = Self::toroidal_distance_sq(self.player.model.position, asteroid.model.position);
+
+            if dist_sq <= radius * radius {
+                self.deaths += 1;
+                self.reset();
+                return;
+            }
+        }
+
+        let mut i = 0;
+        self.asteroids.retain(|_| { let keep = !asteroid_hit[i]; i += 1; keep });
+        let mut i = 0;
+        self.bullets.retain(|_| { let keep = !bullet_spent[i]; i += 1; keep });
+        self.asteroids.extend(fragments);
+
+        if self.asteroids.is_empty() {
+            self.levelup();
+        }
     }
 
     pub fn render(&self,d: &mut impl RaylibDraw){
@@ -286,10 +703,40 @@ impl Game {
         for asteroid in &self.asteroids {
             asteroid.render(d);
         }
+
+        for bullet in &self.bullets {
+            bullet.render(d);
+        }
+
+        if self.debug {
+            self.player.render_rays(d, &self.asteroids);
+        }
     }
 
     pub fn pause(&mut self,time: f64){
         self.pause_end = time;
         self.pause = true;
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toroidal_distance_prefers_the_shorter_wrap_around_path() {
+        let a = Vector2::new(5.0, 5.0);
+        let b = Vector2::new(Game::WIDTH as f32 - 5.0, 5.0);
+
+        // direct distance is WIDTH - 10, but wrapping around the edge is only 10
+        assert_eq!(Game::toroidal_distance_sq(a, b), 10.0 * 10.0);
+    }
+
+    #[test]
+    fn toroidal_distance_matches_direct_distance_away_from_edges() {
+        let a = Vector2::new(100.0, 100.0);
+        let b = Vector2::new(130.0, 140.0);
+
+        assert_eq!(Game::toroidal_distance_sq(a, b), 30.0 * 30.0 + 40.0 * 40.0);
+    }
 }
\ No newline at end of file
@@ -1,10 +1,39 @@
 use game::*;
+use brain::{NN, Population};
 use raylib::prelude::*;
 use raylib::ffi;
+use std::env;
 
 pub mod game;
+pub mod brain;
+
+/// Builds a brain's network input from the player's raycast sensors.
+fn sense(game: &Game) -> Vec<f32> {
+    game.player.sense(&game.asteroids)
+}
+
+/// Trains a population of autopilots headlessly (no vsync wait between
+/// frames) for `generations` rounds and returns the fittest survivor.
+fn train(generations: u32) -> NN {
+    const POPULATION_SIZE: usize = 50;
+    const MAX_FRAMES: u32 = 60 * 30; // 30 simulated seconds per pilot
+
+    let mut population = Population::new(POPULATION_SIZE, vec![Player::RAY_COUNT, 12, 4]);
+
+    for gen in 0..generations {
+        let scores = population.run_generation(MAX_FRAMES, sense);
+        let best = scores.iter().cloned().fold(f32::MIN, f32::max);
+        println!("generation {}: best fitness {:.1}", gen, best);
+
+        population.evolve(&scores);
+    }
+
+    population.brains()[0].clone()
+}
 
 fn main() {
+    let train_mode = env::args().any(|arg| arg == "--train");
+
     let (mut rl, thread) = raylib::init()
         .vsync()
         .size(Game::WIDTH as i32, Game::HEIGHT as i32)
@@ -20,14 +49,36 @@ fn main() {
 
     game.levelup();
 
+    // in --train mode, a genetic algorithm pilots the ship instead of the keyboard
+    let pilot = if train_mode {
+        const GENERATIONS: u32 = 50;
+        Some(train(GENERATIONS))
+    } else {
+        None
+    };
+
     let mut render_texture = rl.load_render_texture(&thread, Game::WIDTH, Game::HEIGHT).unwrap();
 
+    // tracks the pilot's raw fire output across frames so Controls::from_outputs
+    // can edge-trigger it the same way the keyboard's is_key_pressed does
+    let mut pilot_was_firing = false;
+
     while !rl.window_should_close() {
 
         let screen_width = rl.get_screen_width();
         let screen_height = rl.get_screen_height();
 
-        game.update(&rl);
+        match &pilot {
+            Some(brain) => {
+                let dt = rl.get_frame_time();
+                let outputs = brain.forward(&sense(&game));
+                let (controls, firing) = Controls::from_outputs(&outputs, pilot_was_firing);
+                pilot_was_firing = firing;
+
+                game.update_headless(dt, controls);
+            }
+            None => game.update(&rl),
+        }
 
         let mut d = rl.begin_drawing(&thread);
 
@@ -43,4 +94,4 @@ fn main() {
               Rectangle::new(0.0, 0.0, screen_width as f32, screen_height as f32),
               Vector2::zero(), 0.0, Color::WHITE);
     }
-}
\ No newline at end of file
+}